@@ -0,0 +1,169 @@
+use crate::{try_parse_values, Series};
+use serde::Deserialize;
+use std::fmt;
+
+const OEIS_SEARCH_URL: &str = "https://oeis.org/search";
+
+/// Errors that can occur while talking to oeis.org.
+#[derive(Debug)]
+pub enum ClientError {
+    Http(reqwest::Error),
+    NotFound(u32),
+    Decode(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(e) => write!(f, "request to oeis.org failed: {}", e),
+            ClientError::NotFound(id) => write!(f, "no sequence found for A{:06}", id),
+            ClientError::Decode(bad) => {
+                write!(f, "malformed numeric token '{}' in oeis.org response", bad)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    number: u32,
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    results: Option<Vec<SearchHit>>,
+}
+
+fn hit_to_series(hit: SearchHit) -> Result<Series, ClientError> {
+    let values = try_parse_values(&hit.data).map_err(ClientError::Decode)?;
+    Ok(Series::new(hit.number, values))
+}
+
+/// A blocking client for the live OEIS search API, for looking up sequences
+/// that aren't present in a local stripped-file dump (see
+/// [`OEISDatabase::from_path`](crate::OEISDatabase::from_path)).
+pub struct OEISClient {
+    http: reqwest::blocking::Client,
+}
+
+impl OEISClient {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Looks up a single sequence by its `A`-number.
+    pub fn lookup_id(&self, id: u32) -> Result<Series, ClientError> {
+        let results = self.search(&format!("id:A{:06}", id))?;
+        results.into_iter().next().ok_or(ClientError::NotFound(id))
+    }
+
+    /// Runs a free-form OEIS search query and decodes every hit into a `Series`.
+    pub fn search(&self, query: &str) -> Result<Vec<Series>, ClientError> {
+        let resp: SearchResponse = self
+            .http
+            .get(OEIS_SEARCH_URL)
+            .query(&[("q", query), ("fmt", "json")])
+            .send()?
+            .json()?;
+
+        resp.results
+            .unwrap_or_default()
+            .into_iter()
+            .map(hit_to_series)
+            .collect()
+    }
+}
+
+impl Default for OEISClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An async variant of [`OEISClient`], enabled by the `async-client` feature.
+#[cfg(feature = "async-client")]
+pub struct AsyncOEISClient {
+    http: reqwest::Client,
+}
+
+#[cfg(feature = "async-client")]
+impl AsyncOEISClient {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn lookup_id(&self, id: u32) -> Result<Series, ClientError> {
+        let results = self.search(&format!("id:A{:06}", id)).await?;
+        results.into_iter().next().ok_or(ClientError::NotFound(id))
+    }
+
+    pub async fn search(&self, query: &str) -> Result<Vec<Series>, ClientError> {
+        let resp: SearchResponse = self
+            .http
+            .get(OEIS_SEARCH_URL)
+            .query(&[("q", query), ("fmt", "json")])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        resp.results
+            .unwrap_or_default()
+            .into_iter()
+            .map(hit_to_series)
+            .collect()
+    }
+}
+
+#[cfg(feature = "async-client")]
+impl Default for AsyncOEISClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NumberValue;
+
+    #[test]
+    fn hit_to_series_decodes_data_field() {
+        let hit = SearchHit {
+            number: 40,
+            data: "2,3,5,7,11".to_string(),
+        };
+        let series = hit_to_series(hit).unwrap();
+        assert_eq!(series.id(), 40);
+        assert_eq!(
+            series.values(),
+            vec![2, 3, 5, 7, 11]
+                .into_iter()
+                .map(NumberValue::InRange)
+                .collect::<Vec<NumberValue>>()
+        );
+    }
+
+    #[test]
+    fn hit_to_series_reports_malformed_token_instead_of_panicking() {
+        let hit = SearchHit {
+            number: 40,
+            data: "2,not-a-number,5".to_string(),
+        };
+        let err = hit_to_series(hit).unwrap_err();
+        assert!(matches!(err, ClientError::Decode(bad) if bad == "not-a-number"));
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{Keyword, NumberValue, OEISDatabase, Series};
+use num_bigint::{BigInt, Sign};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// The on-disk encodings that [`to_writer`]/[`from_reader`] support.
+///
+/// `Json` and `Ron` are self-describing and round-trip losslessly, same as
+/// `Binary`. `Binary` is a compact, hand-rolled format specific to this
+/// crate (tag byte + varint for `InRange`, tag byte + sign + length-prefixed
+/// magnitude for `OutOfRange`) whose only advantage over the other two is
+/// that it's smaller on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Ron,
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum CodecError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    RonSer(ron::Error),
+    RonDe(ron::de::SpannedError),
+    Truncated,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Io(e) => write!(f, "io error: {}", e),
+            CodecError::Json(e) => write!(f, "json error: {}", e),
+            CodecError::RonSer(e) => write!(f, "ron error: {}", e),
+            CodecError::RonDe(e) => write!(f, "ron error: {}", e),
+            CodecError::Truncated => write!(f, "binary stream ended unexpectedly"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl From<io::Error> for CodecError {
+    fn from(e: io::Error) -> Self {
+        CodecError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CodecError {
+    fn from(e: serde_json::Error) -> Self {
+        CodecError::Json(e)
+    }
+}
+
+impl From<ron::Error> for CodecError {
+    fn from(e: ron::Error) -> Self {
+        CodecError::RonSer(e)
+    }
+}
+
+impl From<ron::de::SpannedError> for CodecError {
+    fn from(e: ron::de::SpannedError) -> Self {
+        CodecError::RonDe(e)
+    }
+}
+
+/// Serializes a database in the given [`Format`].
+pub fn to_writer<W: Write>(db: &OEISDatabase, format: Format, mut writer: W) -> Result<(), CodecError> {
+    match format {
+        Format::Json => Ok(serde_json::to_writer(writer, db)?),
+        Format::Ron => Ok(ron::ser::to_writer(writer, db)?),
+        Format::Binary => write_database(db, &mut writer),
+    }
+}
+
+/// Deserializes a database previously written with [`to_writer`] in the same
+/// [`Format`].
+pub fn from_reader<R: Read>(format: Format, mut reader: R) -> Result<OEISDatabase, CodecError> {
+    match format {
+        Format::Json => Ok(serde_json::from_reader(reader)?),
+        Format::Ron => Ok(ron::de::from_reader(reader)?),
+        Format::Binary => read_database(&mut reader),
+    }
+}
+
+const TAG_IN_RANGE: u8 = 0;
+const TAG_OUT_OF_RANGE: u8 = 1;
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u128) -> Result<(), CodecError> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            writer.write_all(&[byte])?;
+            return Ok(());
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+/// Largest number of bytes a well-formed varint can use to
+/// encode a `u128` (`ceil(128 / 7)`); used to reject corrupted/truncated
+/// input that never sets its terminating bit instead of shifting past the
+/// width of `u128`.
+const MAX_VARINT_BYTES: usize = 19;
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u128, CodecError> {
+    let mut result: u128 = 0;
+    let mut shift = 0;
+    for _ in 0..MAX_VARINT_BYTES {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte).map_err(|_| CodecError::Truncated)?;
+        result |= u128::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+    }
+    Err(CodecError::Truncated)
+}
+
+fn zigzag_encode(v: i128) -> u128 {
+    ((v << 1) ^ (v >> 127)) as u128
+}
+
+fn zigzag_decode(v: u128) -> i128 {
+    ((v >> 1) as i128) ^ -((v & 1) as i128)
+}
+
+fn write_number_value<W: Write>(value: &NumberValue, writer: &mut W) -> Result<(), CodecError> {
+    match value {
+        NumberValue::InRange(n) => {
+            writer.write_all(&[TAG_IN_RANGE])?;
+            write_varint(writer, zigzag_encode(*n))
+        }
+        NumberValue::OutOfRange(n) => {
+            writer.write_all(&[TAG_OUT_OF_RANGE])?;
+            let (sign, magnitude) = n.to_bytes_be();
+            let sign_byte: u8 = match sign {
+                Sign::Minus => 0,
+                Sign::NoSign => 1,
+                Sign::Plus => 2,
+            };
+            writer.write_all(&[sign_byte])?;
+            write_varint(writer, magnitude.len() as u128)?;
+            writer.write_all(&magnitude)?;
+            Ok(())
+        }
+    }
+}
+
+fn read_number_value<R: Read>(reader: &mut R) -> Result<NumberValue, CodecError> {
+    let mut tag = [0u8; 1];
+    reader.read_exact(&mut tag).map_err(|_| CodecError::Truncated)?;
+    match tag[0] {
+        TAG_IN_RANGE => Ok(NumberValue::InRange(zigzag_decode(read_varint(reader)?))),
+        TAG_OUT_OF_RANGE => {
+            let mut sign_byte = [0u8; 1];
+            reader.read_exact(&mut sign_byte).map_err(|_| CodecError::Truncated)?;
+            let sign = match sign_byte[0] {
+                0 => Sign::Minus,
+                1 => Sign::NoSign,
+                _ => Sign::Plus,
+            };
+            let len = read_varint(reader)? as usize;
+            let mut magnitude = vec![0u8; len];
+            reader.read_exact(&mut magnitude).map_err(|_| CodecError::Truncated)?;
+            Ok(NumberValue::OutOfRange(BigInt::from_bytes_be(sign, &magnitude)))
+        }
+        _ => Err(CodecError::Truncated),
+    }
+}
+
+fn write_option_string<W: Write>(value: Option<&str>, writer: &mut W) -> Result<(), CodecError> {
+    match value {
+        Some(s) => {
+            writer.write_all(&[1])?;
+            write_string(s, writer)
+        }
+        None => Ok(writer.write_all(&[0])?),
+    }
+}
+
+fn read_option_string<R: Read>(reader: &mut R) -> Result<Option<String>, CodecError> {
+    let mut tag = [0u8; 1];
+    reader.read_exact(&mut tag).map_err(|_| CodecError::Truncated)?;
+    match tag[0] {
+        0 => Ok(None),
+        _ => Ok(Some(read_string(reader)?)),
+    }
+}
+
+fn write_string<W: Write>(s: &str, writer: &mut W) -> Result<(), CodecError> {
+    let bytes = s.as_bytes();
+    write_varint(writer, bytes.len() as u128)?;
+    Ok(writer.write_all(bytes)?)
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String, CodecError> {
+    let len = read_varint(reader)? as usize;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes).map_err(|_| CodecError::Truncated)?;
+    String::from_utf8(bytes).map_err(|_| CodecError::Truncated)
+}
+
+fn write_offset_component<W: Write>(writer: &mut W, value: i64) -> Result<(), CodecError> {
+    write_varint(writer, zigzag_encode(value as i128))
+}
+
+fn read_offset_component<R: Read>(reader: &mut R) -> Result<i64, CodecError> {
+    Ok(zigzag_decode(read_varint(reader)?) as i64)
+}
+
+fn write_series<W: Write>(series: &Series, writer: &mut W) -> Result<(), CodecError> {
+    write_varint(writer, series.id() as u128)?;
+    let values = series.values();
+    write_varint(writer, values.len() as u128)?;
+    for value in &values {
+        write_number_value(value, writer)?;
+    }
+
+    write_option_string(series.name(), writer)?;
+
+    let keywords = series.keywords();
+    write_varint(writer, keywords.len() as u128)?;
+    for keyword in keywords {
+        write_string(&keyword.0, writer)?;
+    }
+
+    let (major, minor) = series.offset();
+    write_offset_component(writer, major)?;
+    write_offset_component(writer, minor)?;
+
+    write_option_string(series.author(), writer)?;
+
+    Ok(())
+}
+
+fn read_series<R: Read>(reader: &mut R) -> Result<Series, CodecError> {
+    let id = read_varint(reader)? as u32;
+    let len = read_varint(reader)? as usize;
+    let mut values = Vec::with_capacity(len);
+    for _ in 0..len {
+        values.push(read_number_value(reader)?);
+    }
+
+    let name = read_option_string(reader)?;
+
+    let keyword_count = read_varint(reader)? as usize;
+    let mut keywords = Vec::with_capacity(keyword_count);
+    for _ in 0..keyword_count {
+        keywords.push(Keyword(read_string(reader)?));
+    }
+
+    let offset = (read_offset_component(reader)?, read_offset_component(reader)?);
+
+    let author = read_option_string(reader)?;
+
+    Ok(Series {
+        id,
+        values,
+        name,
+        keywords,
+        offset,
+        author,
+    })
+}
+
+fn write_database<W: Write>(db: &OEISDatabase, writer: &mut W) -> Result<(), CodecError> {
+    write_varint(writer, db.series.len() as u128)?;
+    for series in &db.series {
+        write_series(series, writer)?;
+    }
+    Ok(())
+}
+
+fn read_database<R: Read>(reader: &mut R) -> Result<OEISDatabase, CodecError> {
+    let len = read_varint(reader)? as usize;
+    let mut series = Vec::with_capacity(len);
+    for _ in 0..len {
+        series.push(read_series(reader)?);
+    }
+    Ok(OEISDatabase { series })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_database() -> OEISDatabase {
+        OEISDatabase {
+            series: vec![
+                Series::new(
+                    344199,
+                    vec![
+                        NumberValue::InRange(18),
+                        NumberValue::InRange(-36),
+                        NumberValue::OutOfRange(
+                            BigInt::parse_bytes(b"123456789012345678901234567890", 10).unwrap(),
+                        ),
+                        NumberValue::OutOfRange(
+                            BigInt::parse_bytes(b"-99999999999999999999999999999", 10).unwrap(),
+                        ),
+                    ],
+                ),
+                Series::new(1, vec![NumberValue::InRange(0)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn binary_round_trips() {
+        let db = sample_database();
+        let mut buf = Vec::new();
+        to_writer(&db, Format::Binary, &mut buf).unwrap();
+        let back = from_reader(Format::Binary, &buf[..]).unwrap();
+        assert_eq!(back.series, db.series);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let db = sample_database();
+        let mut buf = Vec::new();
+        to_writer(&db, Format::Json, &mut buf).unwrap();
+        let back = from_reader(Format::Json, &buf[..]).unwrap();
+        assert_eq!(back.series, db.series);
+    }
+
+    #[test]
+    fn binary_round_trip_preserves_internal_record_metadata() {
+        let series = Series {
+            id: 40,
+            values: vec![NumberValue::InRange(2), NumberValue::InRange(3)],
+            name: Some("The prime numbers.".to_string()),
+            keywords: vec![Keyword("nonn".to_string()), Keyword("core".to_string())],
+            offset: (1, 1),
+            author: Some("N. J. A. Sloane".to_string()),
+        };
+        let db = OEISDatabase {
+            series: vec![series],
+        };
+
+        let mut buf = Vec::new();
+        to_writer(&db, Format::Binary, &mut buf).unwrap();
+        let back = from_reader(Format::Binary, &buf[..]).unwrap();
+        assert_eq!(back.series, db.series);
+    }
+
+    #[test]
+    fn read_varint_reports_truncated_instead_of_panicking_on_a_corrupted_stream() {
+        let corrupted = [0x80u8; MAX_VARINT_BYTES + 1];
+        let err = read_varint(&mut &corrupted[..]).unwrap_err();
+        assert!(matches!(err, CodecError::Truncated));
+    }
+
+    #[test]
+    fn ron_round_trips() {
+        let db = sample_database();
+        let mut buf = Vec::new();
+        to_writer(&db, Format::Ron, &mut buf).unwrap();
+        let back = from_reader(Format::Ron, &buf[..]).unwrap();
+        assert_eq!(back.series, db.series);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{NumberValue, OEISDatabase, Series};
+use std::collections::{HashMap, HashSet};
+
+/// The longest window length hashed by [`OEISDatabase::index`].
+pub const DEFAULT_MAX_WINDOW_LEN: usize = 8;
+
+/// A reusable lookup structure for finding series that begin with, or
+/// contain, a given run of terms.
+///
+/// Built by hashing every fixed-length window (lengths `1..=max_window_len`)
+/// of each series' values into a `HashMap<Vec<NumberValue>, Vec<usize>>`
+/// mapping a window to the indices of series it occurs in. This trades
+/// memory for query speed: a series with `n` terms contributes roughly
+/// `n * max_window_len` map entries, so a large database or a generous
+/// `max_window_len` can use a lot of memory. Build via
+/// [`OEISDatabase::index`] or [`OEISDatabase::index_with_max_window`].
+pub struct SeriesIndex<'db> {
+    database: &'db OEISDatabase,
+    max_window_len: usize,
+    windows: HashMap<Vec<NumberValue>, Vec<usize>>,
+}
+
+impl<'db> SeriesIndex<'db> {
+    pub(crate) fn build(database: &'db OEISDatabase, max_window_len: usize) -> Self {
+        let mut windows: HashMap<Vec<NumberValue>, Vec<usize>> = HashMap::new();
+        for (i, series) in database.series.iter().enumerate() {
+            let values = &series.values();
+            for len in 1..=max_window_len.min(values.len()) {
+                for window in values.windows(len) {
+                    windows.entry(window.to_vec()).or_default().push(i);
+                }
+            }
+        }
+
+        Self {
+            database,
+            max_window_len,
+            windows,
+        }
+    }
+
+    /// Returns every series whose values start with `query`.
+    pub fn search_prefix(&self, query: &[NumberValue]) -> Vec<&'db Series> {
+        self.candidates(query)
+            .into_iter()
+            .filter(|s| {
+                let values = s.values();
+                values.len() >= query.len() && values[..query.len()] == *query
+            })
+            .collect()
+    }
+
+    /// Returns every series that contains `query` as a contiguous run
+    /// anywhere in its values.
+    pub fn search_contains(&self, query: &[NumberValue]) -> Vec<&'db Series> {
+        self.candidates(query)
+            .into_iter()
+            .filter(|s| s.values().windows(query.len()).any(|w| w == query))
+            .collect()
+    }
+
+    /// Looks up the first `min(query.len(), max_window_len)` terms of
+    /// `query` in the window map, deduplicating series that matched more
+    /// than once.
+    fn candidates(&self, query: &[NumberValue]) -> Vec<&'db Series> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let probe_len = query.len().min(self.max_window_len);
+        let mut seen = HashSet::new();
+        self.windows
+            .get(&query[..probe_len])
+            .into_iter()
+            .flatten()
+            .filter(|&&i| seen.insert(i))
+            .map(|&i| &self.database.series[i])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn sample_database() -> OEISDatabase {
+        OEISDatabase {
+            series: vec![
+                Series::from_str("A344199 ,18,36,60,252,708,834,900,2178,7722,7980,").unwrap(),
+                Series::from_str("A000001 ,0,1,1,1,2,1,2,1,5,2,2,1,5,1,2,1,14,").unwrap(),
+            ],
+        }
+    }
+
+    fn values(ns: &[i128]) -> Vec<NumberValue> {
+        ns.iter().map(|&n| NumberValue::InRange(n)).collect()
+    }
+
+    #[test]
+    fn search_prefix_finds_series_starting_with_query() {
+        let db = sample_database();
+        let idx = db.index();
+        let hits = idx.search_prefix(&values(&[18, 36, 60]));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id(), 344199);
+    }
+
+    #[test]
+    fn search_prefix_rejects_a_matching_run_that_isnt_a_prefix() {
+        let db = sample_database();
+        let idx = db.index();
+        let hits = idx.search_prefix(&values(&[834, 900]));
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn search_contains_finds_a_run_anywhere_in_the_series() {
+        let db = sample_database();
+        let idx = db.index();
+        let hits = idx.search_contains(&values(&[834, 900]));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id(), 344199);
+    }
+
+    #[test]
+    fn search_contains_finds_nothing_for_an_absent_run() {
+        let db = sample_database();
+        let idx = db.index();
+        let hits = idx.search_contains(&values(&[9999, 9998]));
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn index_with_max_window_caps_the_probe_length() {
+        let db = sample_database();
+        // A query longer than max_window_len should still only probe the
+        // first `max_window_len` terms, then filter by full comparison.
+        let idx = db.index_with_max_window(2);
+        let hits = idx.search_prefix(&values(&[18, 36, 60, 252]));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id(), 344199);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{try_parse_values, ParseSeriesError, Series};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// One `%K` classification token from an OEIS internal record (e.g. `nonn`,
+/// `core`, `easy`). Kept as a thin wrapper around the raw token rather than
+/// an exhaustive enum, since OEIS adds new keywords over time.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keyword(pub String);
+
+impl Series {
+    /// Parses a full OEIS internal/"names" record, i.e. the multi-line,
+    /// `%`-tagged format (as opposed to the single comma-separated data line
+    /// handled by [`FromStr`](std::str::FromStr)). Each line is routed to a
+    /// setter by its tag letter:
+    ///
+    /// - `%I` — the `A`-number that anchors every other line in the record
+    /// - `%S`/`%T`/`%U` — the data line, which may be split across up to
+    ///   three lines
+    /// - `%N` — the one-line name/description
+    /// - `%K` — a comma-separated list of keywords
+    /// - `%O` — the offset, `first,second`
+    /// - `%A` — the author line
+    ///
+    /// Unrecognized tags are ignored so the parser keeps working as OEIS
+    /// adds new ones.
+    pub fn from_internal_str(s: &str) -> Result<Self, ParseSeriesError> {
+        lazy_static! {
+            static ref LINE_RE: Regex =
+                Regex::new(r#"^%(?P<tag>[A-Za-z])\s+A(?P<id>\d{6})\s?(?P<rest>.*)$"#).unwrap();
+        }
+
+        let mut id = None;
+        let mut data = String::new();
+        let mut name = None;
+        let mut keywords = Vec::new();
+        let mut offset = (0i64, 0i64);
+        let mut author = None;
+
+        for line in s.lines() {
+            let caps = match LINE_RE.captures(line.trim_end()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            id.get_or_insert_with(|| caps.name("id").unwrap().as_str().parse::<u32>().unwrap());
+            let rest = caps.name("rest").unwrap().as_str().trim();
+
+            match caps.name("tag").unwrap().as_str() {
+                "S" | "T" | "U" => data.push_str(rest),
+                "N" => name = Some(rest.to_string()),
+                "K" => keywords = parse_keywords(rest),
+                "O" => offset = parse_offset(rest),
+                "A" => author = Some(rest.to_string()),
+                _ => {}
+            }
+        }
+
+        let id = id.ok_or_else(|| ParseSeriesError(s.to_string()))?;
+        let values = try_parse_values(&data).map_err(|_| ParseSeriesError(s.to_string()))?;
+        Ok(Self {
+            id,
+            values,
+            name,
+            keywords,
+            offset,
+            author,
+        })
+    }
+}
+
+fn parse_keywords(s: &str) -> Vec<Keyword> {
+    s.split(',')
+        .filter(|k| !k.is_empty())
+        .map(|k| Keyword(k.to_string()))
+        .collect()
+}
+
+fn parse_offset(s: &str) -> (i64, i64) {
+    let mut parts = s.split(',').map(|p| p.trim().parse::<i64>().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NumberValue;
+
+    #[test]
+    fn from_internal_str_parses_every_tagged_field() {
+        let record = "\
+%I A000040
+%S A000040 2,3,5,7,11,13,17,19,23,29,
+%N A000040 The prime numbers.
+%K A000040 nonn,core
+%O A000040 1,1
+%A A000040 N. J. A. Sloane
+";
+        let s = Series::from_internal_str(record).unwrap();
+        assert_eq!(s.id(), 40);
+        assert_eq!(
+            s.values(),
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+                .into_iter()
+                .map(NumberValue::InRange)
+                .collect::<Vec<NumberValue>>()
+        );
+        assert_eq!(s.name(), Some("The prime numbers."));
+        assert_eq!(
+            s.keywords(),
+            &[Keyword("nonn".to_string()), Keyword("core".to_string())]
+        );
+        assert_eq!(s.offset(), (1, 1));
+        assert_eq!(s.author(), Some("N. J. A. Sloane"));
+    }
+
+    #[test]
+    fn from_internal_str_joins_data_across_s_and_t_lines() {
+        let record = "\
+%I A000001
+%S A000001 0,1,1,1,2,1,2,1,5,2,2,1,5,1,2,1,14,1,5,1,5,2,2,1,15,2,2,5,4,1,4,1,51,1,2,1,14,1,2,2,14,1,6,1,4,2,2,1,52,
+%T A000001 2,5,1,5,1,15,2,13,2,2,1,13,1,2,4,267,1,4,1,5,1,4,1,50,1,2,3,4,1,6,1,52,15,2,1,15,1,2,1,12,1,10,1,4,2,
+%N A000001 Number of groups of order n.
+";
+        let s = Series::from_internal_str(record).unwrap();
+        assert_eq!(s.id(), 1);
+        assert_eq!(s.values().len(), 94);
+        assert_eq!(s.name(), Some("Number of groups of order n."));
+    }
+
+    #[test]
+    fn from_internal_str_rejects_record_without_an_id() {
+        let err = Series::from_internal_str("this has no tagged lines at all").unwrap_err();
+        assert!(!err.to_string().is_empty());
+    }
+
+    #[test]
+    fn from_internal_str_reports_malformed_numeric_token_instead_of_panicking() {
+        let record = "%S A000040 2,not-a-number,5,";
+        let err = Series::from_internal_str(record).unwrap_err();
+        assert_eq!(err.0, record);
+    }
+}
@@ -1,22 +1,55 @@
 use lazy_static::lazy_static;
-use num_bigint::{BigInt, Sign};
+use num_bigint::BigInt;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+mod client;
+pub use client::{ClientError, OEISClient};
+
+#[cfg(feature = "async-client")]
+pub use client::AsyncOEISClient;
+
+mod codec;
+pub use codec::{from_reader, to_writer, CodecError, Format};
+
+mod internal;
+pub use internal::Keyword;
+
+mod index;
+pub use index::{SeriesIndex, DEFAULT_MAX_WINDOW_LEN};
+
+#[derive(Serialize, Deserialize)]
 pub struct OEISDatabase {
     pub series: Vec<Series>,
 }
 
-enum ReadingError {
+#[derive(Debug)]
+pub enum ReadingError {
     FileOpenIO(std::io::Error),
     ReadFileError(std::io::Error),
     RegexParseError(usize, String),
 }
 
+impl fmt::Display for ReadingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadingError::FileOpenIO(e) => write!(f, "failed to open file: {}", e),
+            ReadingError::ReadFileError(e) => write!(f, "failed to read line: {}", e),
+            ReadingError::RegexParseError(line, contents) => {
+                write!(f, "failed to parse line {}: '{}'", line, contents)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadingError {}
+
 impl OEISDatabase {
     pub fn series(&self) -> Vec<Series> {
         self.series.clone()
@@ -39,31 +72,147 @@ impl OEISDatabase {
             Err(e) => Err(e),
         }
     }
+
+    /// Like [`from_path`](Self::from_path), but reports exactly which line
+    /// failed instead of panicking: a line that fails the regex yields
+    /// `ReadingError::RegexParseError(line_number, line_contents)`.
+    pub fn from_path_checked(path: &PathBuf) -> Result<Self, ReadingError> {
+        let f = File::open(path).map_err(ReadingError::FileOpenIO)?;
+        let reader = BufReader::new(f);
+
+        let mut series = Vec::new();
+        let mut past_header = false;
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(ReadingError::ReadFileError)?;
+            if !past_header && line.starts_with('#') {
+                continue;
+            }
+            past_header = true;
+
+            match Series::from_str(&line) {
+                Ok(s) => series.push(s),
+                Err(ParseSeriesError(contents)) => {
+                    return Err(ReadingError::RegexParseError(i + 1, contents))
+                }
+            }
+        }
+
+        Ok(Self { series })
+    }
+
+    /// Builds a [`SeriesIndex`] using [`DEFAULT_MAX_WINDOW_LEN`] as the
+    /// longest window length.
+    pub fn index(&self) -> SeriesIndex<'_> {
+        self.index_with_max_window(DEFAULT_MAX_WINDOW_LEN)
+    }
+
+    /// Like [`index`](Self::index), but lets the caller cap the longest
+    /// window length hashed, trading index memory for query reach.
+    pub fn index_with_max_window(&self, max_window_len: usize) -> SeriesIndex<'_> {
+        SeriesIndex::build(self, max_window_len.max(1))
+    }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NumberValue {
-    InRange(i128),
+    InRange(#[serde(with = "i128_as_str")] i128),
     OutOfRange(BigInt),
 }
 
-#[derive(Debug, Clone, Hash)]
+/// Serde `with` module that carries an `i128` as a decimal string.
+///
+/// Several non-binary serde formats (notably RON) don't implement
+/// `serialize_i128`/`deserialize_i128` at all, which would otherwise make
+/// it impossible to serialize a `NumberValue::InRange` in those formats.
+mod i128_as_str {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i128, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parses a comma-separated run of integers (as found in both the stripped
+/// data line and the OEIS JSON `data` field) into `NumberValue`s, splitting
+/// each term into `InRange`/`OutOfRange` depending on whether it fits in an
+/// `i128`. Returns the offending token on the first one that's neither a
+/// valid `i128` nor a valid base-10 `BigInt`.
+pub(crate) fn try_parse_values(s: &str) -> Result<Vec<NumberValue>, String> {
+    s.split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<i128>() {
+            Ok(n) => Ok(NumberValue::InRange(n)),
+            Err(_) => BigInt::parse_bytes(s.as_bytes(), 10)
+                .map(NumberValue::OutOfRange)
+                .ok_or_else(|| s.to_string()),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Series {
     id: u32,
     values: Vec<NumberValue>,
+    name: Option<String>,
+    keywords: Vec<Keyword>,
+    offset: (i64, i64),
+    author: Option<String>,
 }
 
 impl Series {
+    pub(crate) fn new(id: u32, values: Vec<NumberValue>) -> Self {
+        Self {
+            id,
+            values,
+            name: None,
+            keywords: Vec::new(),
+            offset: (0, 0),
+            author: None,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
     pub fn values(&self) -> Vec<NumberValue> {
         self.values.clone()
     }
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    pub fn keywords(&self) -> &[Keyword] {
+        &self.keywords
+    }
+    pub fn offset(&self) -> (i64, i64) {
+        self.offset
+    }
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+}
+
+/// The offending input, returned when a `Series` can't be parsed out of it:
+/// the line given to `from_str`, or the whole record given to
+/// `from_internal_str`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseSeriesError(pub(crate) String);
+
+impl fmt::Display for ParseSeriesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse series from: '{}'", self.0)
+    }
 }
 
+impl std::error::Error for ParseSeriesError {}
+
 impl FromStr for Series {
-    type Err = ();
+    type Err = ParseSeriesError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -72,24 +221,12 @@ impl FromStr for Series {
         // println!("trying to parse {}", s);
         let caps = RE.captures(s);
         match caps {
-            Some(m) => Ok(Self {
-                id: m.name("Id").unwrap().as_str().parse().unwrap(),
-                values: m
-                    .name("vals")
-                    .unwrap()
-                    .as_str()
-                    .split(',')
-                    .filter(|s| !s.is_empty())
-                    // .map(|s| BigInt::parse_bytes(s.as_bytes(), 10).unwrap())
-                    .map(|s| match s.parse::<i128>() {
-                        Ok(n) => NumberValue::InRange(n),
-                        Err(_) => {
-                            NumberValue::OutOfRange(BigInt::parse_bytes(s.as_bytes(), 10).unwrap())
-                        }
-                    })
-                    .collect(),
-            }),
-            None => Err(()),
+            Some(m) => Ok(Self::new(
+                m.name("Id").unwrap().as_str().parse().unwrap(),
+                try_parse_values(m.name("vals").unwrap().as_str())
+                    .map_err(|_| ParseSeriesError(s.to_string()))?,
+            )),
+            None => Err(ParseSeriesError(s.to_string())),
         }
     }
 }
@@ -104,7 +241,7 @@ mod tests {
         assert_eq!(s.id(), 344199);
         assert_eq!(
             s.values(),
-            vec![18, 36, 60, 252, 708, 834, 900, 2178, 7722, 7980]
+            [18, 36, 60, 252, 708, 834, 900, 2178, 7722, 7980]
                 .iter()
                 .map(|e| NumberValue::InRange(*e))
                 .collect::<Vec<NumberValue>>()
@@ -129,4 +266,17 @@ mod tests {
             .collect::<Vec<NumberValue>>()
         );
     }
+
+    #[test]
+    fn from_str_reports_offending_line() {
+        let err = Series::from_str("not a series line").unwrap_err();
+        assert_eq!(err.0, "not a series line");
+    }
+
+    #[test]
+    fn from_str_reports_malformed_numeric_token_instead_of_panicking() {
+        let text = "A344199 ,18,36,60,25?,708,";
+        let err = Series::from_str(text).unwrap_err();
+        assert_eq!(err.0, text);
+    }
 }